@@ -13,6 +13,22 @@ pub type Result<T> = anyhow::Result<T>;
 pub const MEBIBYTE: u64 = 1024 * 1024;
 pub const PART_SIZE: u64 = 8 * MEBIBYTE;
 
+/// S3's hard ceiling on the number of parts in a multipart upload/download.
+pub const MAX_UPLOAD_PARTS: u64 = 10_000;
+
+/// Compute a per-task part size that respects S3's `MAX_UPLOAD_PARTS` ceiling.
+/// If `base_part_size` would produce more than `MAX_UPLOAD_PARTS` parts for an
+/// object this size (e.g. an 80GiB+ object at the default 8MiB part size),
+/// bump it up just enough to fit, rounded up to a MiB boundary.
+pub fn effective_part_size(base_part_size: u64, size: u64) -> u64 {
+    if base_part_size == 0 || size.div_ceil(base_part_size) <= MAX_UPLOAD_PARTS {
+        return base_part_size;
+    }
+
+    let min_part_size = (size / MAX_UPLOAD_PARTS) + 1;
+    min_part_size.div_ceil(MEBIBYTE) * MEBIBYTE
+}
+
 /// Used when the runner knows it can't run a workload.
 /// It's not the user's fault, it's not a bug.
 #[derive(thiserror::Error, Debug)]
@@ -42,6 +58,18 @@ pub struct BenchmarkConfig {
     pub region: String,
     pub target_throughput_gigabits_per_sec: f64,
     pub disable_directory: bool,
+    /// Base part size for multipart upload/download. Bumped up per-task by
+    /// `effective_part_size` when a task's size would otherwise need more than
+    /// `MAX_UPLOAD_PARTS` parts. Defaults to `PART_SIZE`, overridable via CLI
+    /// so a part size sweep doesn't require editing the workload file.
+    pub base_part_size: u64,
+    /// Fixed concurrent-transfer count, overriding the default
+    /// `TargetThroughput`-derived concurrency mode. `None` keeps the
+    /// existing behavior.
+    pub concurrency_override: Option<usize>,
+    /// Per-transfer read buffer size, in bytes, overriding the transfer
+    /// manager's own default. `None` keeps the existing behavior.
+    pub buffer_size_override: Option<u64>,
 }
 
 /// From the workload's JSON file
@@ -53,11 +81,17 @@ pub struct WorkloadConfig {
     pub checksum: Option<String>,
     pub max_repeat_count: u32,
     pub max_repeat_secs: f64,
+    /// Explicit tasks. Left empty when `discover` is used instead.
+    #[serde(default)]
     pub tasks: Vec<TaskConfig>,
+    /// Alternative to listing `tasks` by hand: discover them at runtime by
+    /// paginating `ListObjectsV2` over a bucket prefix. See `DiscoverConfig`.
+    #[serde(default)]
+    pub discover: Option<DiscoverConfig>,
 }
 
 /// A task in the workload's JSON file
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TaskConfig {
     pub action: TaskAction,
@@ -65,22 +99,56 @@ pub struct TaskConfig {
     pub size: u64,
 }
 
+/// Discover tasks at runtime instead of listing them in the workload file:
+/// every object under `prefix` becomes a `TaskConfig` with the given
+/// `action`, sized from `ListObjectsV2`. Lets a workload run against a real,
+/// already-populated dataset without hand-writing one `TaskConfig` per
+/// object.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiscoverConfig {
+    pub prefix: String,
+    pub action: TaskAction,
+}
+
 /// Possible values for the "action" field of the workload's JSON file
-#[derive(Debug, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub enum TaskAction {
     Download,
     Upload,
+    Delete,
+}
+
+/// Per-action timing and byte totals for a single `RunBenchmark::run()` call.
+/// Download and upload run as independent concurrency domains (see
+/// `BenchmarkConfig::throughput_for_action`), so a workload that mixes both
+/// actions reports each domain's elapsed time and bytes separately instead of
+/// one aggregate number. Deletes run as batched `DeleteObjects` calls (up to
+/// 1000 keys each) rather than a per-task spawn, so they get a count instead
+/// of a byte total.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RunStats {
+    pub download_secs: f64,
+    pub download_bytes: u64,
+    pub upload_secs: f64,
+    pub upload_bytes: u64,
+    pub delete_secs: f64,
+    pub delete_count: u64,
 }
 
 /// All benchmark configuration (combination of json workload and command line args)
 impl BenchmarkConfig {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         workload_path: &str,
         bucket: &str,
         region: &str,
         target_throughput_gigabits_per_sec: f64,
         disable_directory: bool,
+        part_size_override: Option<u64>,
+        concurrency_override: Option<usize>,
+        buffer_size_override: Option<u64>,
     ) -> Result<Self> {
         let json_file = File::open(workload_path)
             .with_context(|| format!("Failed opening '{workload_path}'"))?;
@@ -108,14 +176,64 @@ impl BenchmarkConfig {
             region: region.to_string(),
             target_throughput_gigabits_per_sec,
             disable_directory,
+            base_part_size: part_size_override.unwrap_or(PART_SIZE),
+            concurrency_override,
+            buffer_size_override,
         })
     }
+
+    /// Split `target_throughput_gigabits_per_sec` across download and upload
+    /// when a workload mixes both actions, proportional to each action's
+    /// share of total bytes, so a slow side (e.g. throttled uploads under
+    /// backoff) doesn't starve or penalize the other's concurrency budget.
+    /// Homogeneous workloads just get the full target, same as before.
+    pub fn throughput_for_action(&self, action: TaskAction) -> f64 {
+        let download_bytes = bytes_for_action(&self.workload, TaskAction::Download);
+        let upload_bytes = bytes_for_action(&self.workload, TaskAction::Upload);
+
+        if download_bytes == 0 || upload_bytes == 0 {
+            return self.target_throughput_gigabits_per_sec;
+        }
+
+        let action_bytes = match action {
+            TaskAction::Download => download_bytes,
+            TaskAction::Upload => upload_bytes,
+            // Deletes aren't bandwidth-limited, so they don't participate in
+            // the download/upload throughput split.
+            TaskAction::Delete => return self.target_throughput_gigabits_per_sec,
+        };
+        let total_bytes = download_bytes + upload_bytes;
+        self.target_throughput_gigabits_per_sec * (action_bytes as f64 / total_bytes as f64)
+    }
+}
+
+fn bytes_for_action(workload: &WorkloadConfig, action: TaskAction) -> u64 {
+    workload
+        .tasks
+        .iter()
+        .filter(|task| task.action == action)
+        .map(|task| task.size)
+        .sum()
 }
 
 #[async_trait]
 pub trait RunBenchmark {
-    async fn run(&self) -> Result<()>;
+    async fn run(&self) -> Result<RunStats>;
     fn config(&self) -> &BenchmarkConfig;
+
+    /// Optional post-run cleanup, e.g. deleting every object the benchmark
+    /// uploaded. Default no-op; runners that can tear down override it.
+    async fn teardown(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Cooperative cancellation hook: ask an in-progress `run()` to stop
+    /// spawning new work and wind down whatever's outstanding, returning the
+    /// partial `RunStats` it has accumulated so far instead of erroring.
+    /// Called from a separate task than the one running `run()`, so
+    /// implementations need to use a thread-safe flag. Default no-op for
+    /// runners that don't support graceful shutdown.
+    fn request_shutdown(&self) {}
 }
 
 // Do prep work between runs, before timers starts (e.g. create intermediate directories)
@@ -144,6 +262,9 @@ pub fn prepare_run(workload: &WorkloadConfig) -> Result<()> {
                         return Err(anyhow!("file not found: {filepath:?}"));
                     }
                 }
+
+                // Nothing to prep on disk: the key is deleted from the bucket, not the filesystem.
+                TaskAction::Delete => {}
             }
         }
     }
@@ -1,12 +1,23 @@
 use clap::{Parser, ValueEnum};
 use std::process::exit;
-use std::time::Instant;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tracing::{info_span, Instrument};
 
 use s3_benchrunner_rust::{
-    bytes_to_gigabits, prepare_run, telemetry, BenchmarkConfig, Result, RunBenchmark,
+    bytes_to_gigabits, prepare_run, telemetry, BenchmarkConfig, Result, RunBenchmark, RunStats,
     SkipBenchmarkError, TransferManagerRunner,
 };
+
+/// Exit code for a benchmark that was cut short by Ctrl-C, distinct from a
+/// clean run (0) or a skipped benchmark ([`SkipBenchmarkError`], 123).
+const INTERRUPTED_EXIT_CODE: i32 = 130; // 128 + SIGINT(2), the usual shell convention
+
+/// How long to wait for `RunBenchmark::run()` to wind down after
+/// `request_shutdown()` before giving up and reporting zero for the run in
+/// progress.
+const SHUTDOWN_GRACE_PERIOD: Duration = Duration::from_secs(5);
+
 #[derive(Parser, Debug)]
 #[command()]
 struct Args {
@@ -20,13 +31,67 @@ struct Args {
     region: String,
     #[arg(help = "Target throughput, in gigabits per second (e.g. \"100.0\" for c5n.18xlarge)")]
     target_throughput: f64,
-    #[arg(long, help = "Emit telemetry via OTLP/gRPC to http://localhost:4317")]
+    #[arg(long, help = "Emit telemetry to a local trace file (see --otlp-endpoint to also stream live via OTLP/gRPC)")]
     telemetry: bool,
+    #[arg(
+        long,
+        num_args = 0..=1,
+        default_missing_value = "http://localhost:4317",
+        help = "With --telemetry, also emit telemetry via OTLP/gRPC to this endpoint (defaults to http://localhost:4317 if given with no value)"
+    )]
+    otlp_endpoint: Option<String>,
+    #[arg(
+        long,
+        help = "With --telemetry, also ship spans to a Datadog agent's trace intake at this URL (e.g. \"http://localhost:8126\")"
+    )]
+    datadog_agent: Option<String>,
+    #[arg(
+        long,
+        help = "With --telemetry, generate AWS X-Ray-compatible trace IDs and also emit a newline-delimited X-Ray segment-document file"
+    )]
+    xray: bool,
+    #[arg(
+        long,
+        help = "With --telemetry, also stream each batch of spans to stderr as newline-delimited OTLP JSON, without buffering the whole run in memory like the queued file exporter does"
+    )]
+    stream_telemetry: bool,
+    #[arg(
+        long,
+        default_value_t = 512,
+        help = "Max number of spans per exported trace batch"
+    )]
+    trace_batch_size: usize,
+    #[arg(
+        long,
+        default_value_t = 2048,
+        help = "Max number of spans buffered by the trace batch processor before it starts dropping spans"
+    )]
+    trace_queue_size: usize,
     #[arg(
         long,
         help = "Instead of using 1 upload_objects()/download_objects() call for multiple files on disk, use N upload()/download() calls."
     )]
     disable_directory: bool,
+    #[arg(
+        long,
+        help = "After the last run, delete every object the workload's upload tasks created, via batched DeleteObjects"
+    )]
+    teardown: bool,
+    #[arg(
+        long,
+        help = "Override base part size, in bytes, for multipart upload/download (default: 8 MiB)"
+    )]
+    part_size: Option<u64>,
+    #[arg(
+        long,
+        help = "Override the transfer manager's concurrency with a fixed number of concurrent transfers, instead of deriving it from the target throughput argument"
+    )]
+    concurrency: Option<usize>,
+    #[arg(
+        long,
+        help = "Override the transfer manager's per-transfer read buffer size, in bytes"
+    )]
+    buffer_size: Option<u64>,
 }
 
 #[derive(ValueEnum, Clone, Debug)]
@@ -59,7 +124,17 @@ async fn main() {
 async fn execute(args: &Args) -> Result<()> {
     let mut telemetry = if args.telemetry {
         // If emitting telemetry, set that up as tracing_subscriber.
-        Some(telemetry::init_tracing_subscriber().unwrap())
+        Some(
+            telemetry::init_tracing_subscriber(
+                args.otlp_endpoint.as_deref(),
+                args.datadog_agent.as_deref(),
+                args.xray,
+                args.stream_telemetry,
+                args.trace_batch_size,
+                args.trace_queue_size,
+            )
+            .unwrap(),
+        )
     } else {
         // Otherwise, set the default subscriber,
         // which prints to stdout if env-var set like RUST_LOG=trace
@@ -71,7 +146,7 @@ async fn execute(args: &Args) -> Result<()> {
     };
 
     // create appropriate benchmark runner
-    let runner = new_runner(args).await?;
+    let runner: Arc<dyn RunBenchmark + Send + Sync> = Arc::from(new_runner(args).await?);
 
     let workload = &runner.config().workload;
     let workload_name = workload_name(&args.workload);
@@ -86,14 +161,34 @@ async fn execute(args: &Args) -> Result<()> {
         let run_start_datetime = chrono::Utc::now();
         let run_start = Instant::now(); // high resolution
 
-        runner
-            .run()
-            .instrument(info_span!(
-                "run-benchmark",
-                num = run_num,
-                workload = workload_name
-            ))
-            .await?;
+        // Run on its own task so a Ctrl-C can be observed concurrently: we
+        // cooperatively ask it to wind down rather than just dropping it,
+        // so whatever RunStats it already accumulated isn't thrown away.
+        let mut run_handle = {
+            let runner = Arc::clone(&runner);
+            let run_span = info_span!("run-benchmark", num = run_num, workload = workload_name);
+            tokio::spawn(async move { runner.run().instrument(run_span).await })
+        };
+
+        let mut interrupted = false;
+        let run_stats = tokio::select! {
+            result = &mut run_handle => result.expect("run-benchmark task panicked")?,
+            _ = tokio::signal::ctrl_c() => {
+                interrupted = true;
+                eprintln!("Received Ctrl-C, winding down in-flight transfers...");
+                runner.request_shutdown();
+
+                match tokio::time::timeout(SHUTDOWN_GRACE_PERIOD, &mut run_handle).await {
+                    Ok(result) => result.expect("run-benchmark task panicked")?,
+                    Err(_) => {
+                        eprintln!(
+                            "Timed out waiting for in-flight transfers to stop; this run's results are incomplete."
+                        );
+                        RunStats::default()
+                    }
+                }
+            }
+        };
 
         let run_secs = run_start.elapsed().as_secs_f64();
 
@@ -104,6 +199,16 @@ async fn execute(args: &Args) -> Result<()> {
                 &run_start_datetime,
                 run_num,
             ));
+            telemetry.flush_metrics_to_file(&metrics_file_name(
+                workload_name,
+                &run_start_datetime,
+                run_num,
+            ));
+            telemetry.flush_xray_segments_to_file(&xray_segments_file_name(
+                workload_name,
+                &run_start_datetime,
+                run_num,
+            ));
         }
 
         eprintln!(
@@ -112,6 +217,37 @@ async fn execute(args: &Args) -> Result<()> {
             run_secs,
             gigabits_per_run / run_secs
         );
+        // Mixed download+upload workloads run as independent concurrency
+        // domains, so report each domain's own timing/throughput too.
+        if run_stats.download_bytes > 0 && run_stats.upload_bytes > 0 {
+            eprintln!(
+                "  download Secs:{:.6} Gb/s:{:.6}",
+                run_stats.download_secs,
+                bytes_to_gigabits(run_stats.download_bytes) / run_stats.download_secs.max(f64::EPSILON)
+            );
+            eprintln!(
+                "  upload   Secs:{:.6} Gb/s:{:.6}",
+                run_stats.upload_secs,
+                bytes_to_gigabits(run_stats.upload_bytes) / run_stats.upload_secs.max(f64::EPSILON)
+            );
+        }
+        if run_stats.delete_count > 0 {
+            eprintln!(
+                "  delete   Secs:{:.6} Count:{}",
+                run_stats.delete_secs, run_stats.delete_count
+            );
+        }
+
+        // Telemetry for this run is already flushed above; exit now instead
+        // of starting another run or tearing down, so a Ctrl-C stays cheap
+        // to recover from during iterative development.
+        if interrupted {
+            eprintln!(
+                "Benchmark interrupted after {run_num} run(s); {} of this run's {bytes_per_run} bytes completed.",
+                run_stats.download_bytes + run_stats.upload_bytes
+            );
+            exit(INTERRUPTED_EXIT_CODE);
+        }
 
         // break out if we've exceeded max_repeat_secs
         if app_start.elapsed().as_secs_f64() >= workload.max_repeat_secs {
@@ -119,20 +255,30 @@ async fn execute(args: &Args) -> Result<()> {
         }
     }
 
+    if args.teardown {
+        runner
+            .teardown()
+            .instrument(info_span!("teardown"))
+            .await?;
+    }
+
     Ok(())
 }
 
-async fn new_runner(args: &Args) -> Result<Box<dyn RunBenchmark>> {
+async fn new_runner(args: &Args) -> Result<Box<dyn RunBenchmark + Send + Sync>> {
     let config = BenchmarkConfig::new(
         &args.workload,
         &args.bucket,
         &args.region,
         args.target_throughput,
         args.disable_directory,
+        args.part_size,
+        args.concurrency,
+        args.buffer_size,
     )?;
     match args.s3_client {
         S3ClientId::TransferManager => {
-            let transfer_manager = TransferManagerRunner::new(config).await;
+            let transfer_manager = TransferManagerRunner::new(config).await?;
             Ok(Box::new(transfer_manager))
         }
     }
@@ -153,3 +299,21 @@ fn trace_file_name(
     let run_start = run_start.format("%Y%m%dT%H%M%SZ").to_string();
     format!("trace_{run_start}_{workload}_run{run_num:02}.json")
 }
+
+fn metrics_file_name(
+    workload: &str,
+    run_start: &chrono::DateTime<chrono::Utc>,
+    run_num: u32,
+) -> String {
+    let run_start = run_start.format("%Y%m%dT%H%M%SZ").to_string();
+    format!("metrics_{run_start}_{workload}_run{run_num:02}.json")
+}
+
+fn xray_segments_file_name(
+    workload: &str,
+    run_start: &chrono::DateTime<chrono::Utc>,
+    run_num: u32,
+) -> String {
+    let run_start = run_start.format("%Y%m%dT%H%M%SZ").to_string();
+    format!("xray_segments_{run_start}_{workload}_run{run_num:02}.ndjson")
+}
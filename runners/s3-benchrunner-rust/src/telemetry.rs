@@ -13,7 +13,66 @@ use std::env;
 use crate::Result;
 
 pub mod common;
+pub mod datadog;
+pub mod metrics;
 pub mod trace;
+pub mod xray;
+
+pub mod trace_exporter;
+
+/// Dispatches to either the default random ID generator, or `xray::XrayIdGenerator`,
+/// depending on whether `--xray` was passed. A trait object won't do here because
+/// `opentelemetry_sdk::trace::Config::with_id_generator` takes `impl IdGenerator`.
+enum IdGenerator {
+    Random(opentelemetry_sdk::trace::RandomIdGenerator),
+    Xray(crate::telemetry::xray::XrayIdGenerator),
+}
+
+impl opentelemetry_sdk::trace::IdGenerator for IdGenerator {
+    fn new_trace_id(&self) -> opentelemetry::trace::TraceId {
+        match self {
+            IdGenerator::Random(g) => g.new_trace_id(),
+            IdGenerator::Xray(g) => g.new_trace_id(),
+        }
+    }
+
+    fn new_span_id(&self) -> opentelemetry::trace::SpanId {
+        match self {
+            IdGenerator::Random(g) => g.new_span_id(),
+            IdGenerator::Xray(g) => g.new_span_id(),
+        }
+    }
+}
+
+// Histogram bucket boundaries for the metrics recorded in `transfer_manager.rs`.
+// Kept here, rather than next to the call sites, since both views have to be
+// registered on the `MeterProvider` before any meter is created.
+const THROUGHPUT_GBPS_BOUNDARIES: [f64; 9] =
+    [0.1, 0.5, 1.0, 2.5, 5.0, 10.0, 25.0, 50.0, 100.0];
+const LATENCY_MS_BOUNDARIES: [f64; 10] =
+    [1.0, 5.0, 10.0, 25.0, 50.0, 100.0, 250.0, 500.0, 1000.0, 5000.0];
+
+// Views that configure our two histograms as explicit-bucket histograms with
+// fixed boundaries, instead of the SDK's default aggregation.
+fn metrics_views() -> Vec<Box<dyn opentelemetry_sdk::metrics::View>> {
+    use opentelemetry_sdk::metrics::{new_view, Aggregation, Instrument, Stream};
+
+    let histogram_view = |name: &'static str, boundaries: Vec<f64>| {
+        new_view(
+            Instrument::new().name(name),
+            Stream::new().aggregation(Aggregation::ExplicitBucketHistogram {
+                boundaries,
+                record_min_max: true,
+            }),
+        )
+        .expect("static view config is always valid")
+    };
+
+    vec![
+        histogram_view("throughput_gbps", THROUGHPUT_GBPS_BOUNDARIES.to_vec()),
+        histogram_view("latency_ms", LATENCY_MS_BOUNDARIES.to_vec()),
+    ]
+}
 
 // Create OTEL Resource (the entity that produces telemetry)
 fn otel_resource() -> opentelemetry_sdk::Resource {
@@ -30,29 +89,144 @@ fn otel_resource() -> opentelemetry_sdk::Resource {
 pub struct Telemetry {
     benchmark_trace_exporter: crate::telemetry::trace::SpanExporter,
     otel_tracer_provider: opentelemetry_sdk::trace::TracerProvider,
+    benchmark_metrics_exporter: crate::telemetry::metrics::MetricsExporter,
+    meter_provider: opentelemetry_sdk::metrics::MeterProvider,
+    /// Only set when `--xray` was passed.
+    xray_segment_exporter: Option<crate::telemetry::xray::SegmentExporter>,
 }
 
 impl Drop for Telemetry {
     fn drop(&mut self) {
+        // Give every batch processor registered on the provider a chance to
+        // hand whatever's left to its exporter before we exit, so OTLP and
+        // Datadog ship spans produced after the last explicit flush (e.g. a
+        // `teardown` span emitted after the repeat loop). The file and X-Ray
+        // exporters only queue those spans in memory; nothing writes them to
+        // a file after the last `flush_to_file`.
+        for flush_result in self.otel_tracer_provider.force_flush() {
+            if let Err(e) = flush_result {
+                eprintln!("Failed to flush telemetry on shutdown: {e:?}");
+                break;
+            }
+        }
+        if let Err(e) = self.meter_provider.shutdown() {
+            eprintln!("Failed to shut down metrics pipeline: {e:?}");
+        }
         opentelemetry::global::shutdown_tracer_provider();
     }
 }
 
-pub fn init_tracing_subscriber() -> Result<Telemetry> {
+pub fn init_tracing_subscriber(
+    otlp_endpoint: Option<&str>,
+    datadog_agent: Option<&str>,
+    xray: bool,
+    stream_telemetry: bool,
+    trace_batch_size: usize,
+    trace_queue_size: usize,
+) -> Result<Telemetry> {
+    use anyhow::Context;
+
     // Create our custom otel span exporter that queues up data until it's told to flush to a file
     let benchmark_trace_exporter = crate::telemetry::trace::SpanExporter::new();
 
+    let id_generator = if xray {
+        IdGenerator::Xray(crate::telemetry::xray::XrayIdGenerator)
+    } else {
+        IdGenerator::Random(opentelemetry_sdk::trace::RandomIdGenerator::default())
+    };
+
+    // Batch spans off the benchmark's critical path, instead of synchronously handing
+    // each ended span straight to the exporter: for a 30GiB download that used to mean
+    // 11,000+ `SdkSpanDataBatch`es, with a mutex lock and `Resource::clone` per span
+    // sitting directly in the timed path.
+    let trace_batch_config = opentelemetry_sdk::trace::BatchConfigBuilder::default()
+        .with_max_export_batch_size(trace_batch_size)
+        .with_max_queue_size(trace_queue_size)
+        .build();
+
+    // `force_flush` on the resulting provider drains this processor's buffer through
+    // the exporter's own `export()`, which is the same queued-batch drain `flush_to_file`
+    // already reads from - no separate plumbing needed for a correct flush at exit.
+    let benchmark_trace_processor = opentelemetry_sdk::trace::BatchSpanProcessor::builder(
+        benchmark_trace_exporter.clone(),
+        opentelemetry_sdk::runtime::Tokio,
+    )
+    .with_batch_config(trace_batch_config.clone())
+    .build();
+
     // Create otel tracer provider, which uses our exporter
-    let otel_tracer_provider = opentelemetry_sdk::trace::TracerProvider::builder()
+    let mut provider_builder = opentelemetry_sdk::trace::TracerProvider::builder()
         .with_config(
             opentelemetry_sdk::trace::Config::default()
-                // If export trace to AWS X-Ray, you can use XrayIdGenerator
-                .with_id_generator(opentelemetry_sdk::trace::RandomIdGenerator::default())
+                .with_id_generator(id_generator)
                 .with_resource(otel_resource()),
         )
-        .with_simple_exporter(benchmark_trace_exporter.clone())
+        .with_span_processor(benchmark_trace_processor);
+
+    // If requested, also stream spans live to a collector over OTLP/gRPC,
+    // batched off the benchmark's critical path instead of exported synchronously.
+    if let Some(endpoint) = otlp_endpoint {
+        use opentelemetry_otlp::WithExportConfig;
+
+        let otlp_exporter = opentelemetry_otlp::new_exporter()
+            .tonic()
+            .with_endpoint(endpoint)
+            .build_span_exporter()
+            .with_context(|| format!("failed building OTLP/gRPC exporter for {endpoint}"))?;
+
+        let otlp_processor = opentelemetry_sdk::trace::BatchSpanProcessor::builder(
+            otlp_exporter,
+            opentelemetry_sdk::runtime::Tokio,
+        )
+        .with_batch_config(trace_batch_config.clone())
+        .build();
+
+        provider_builder = provider_builder.with_span_processor(otlp_processor);
+    }
+
+    // If requested, also ship spans to a Datadog agent's trace intake.
+    if let Some(agent_url) = datadog_agent {
+        let datadog_exporter = crate::telemetry::datadog::DatadogExporter::new(agent_url);
+
+        let datadog_processor = opentelemetry_sdk::trace::BatchSpanProcessor::builder(
+            datadog_exporter,
+            opentelemetry_sdk::runtime::Tokio,
+        )
+        .with_batch_config(trace_batch_config.clone())
         .build();
 
+        provider_builder = provider_builder.with_span_processor(datadog_processor);
+    }
+
+    // If requested, also queue up spans as AWS X-Ray segment documents
+    // (meaningful once `id_generator` above is producing X-Ray-compatible trace IDs).
+    // Batched like every other processor above, instead of a simple exporter,
+    // so `--xray` doesn't put the per-span mutex lock back on the critical path.
+    let xray_segment_exporter = if xray {
+        let exporter = crate::telemetry::xray::SegmentExporter::new();
+        let xray_processor = opentelemetry_sdk::trace::BatchSpanProcessor::builder(
+            exporter.clone(),
+            opentelemetry_sdk::runtime::Tokio,
+        )
+        .with_batch_config(trace_batch_config)
+        .build();
+        provider_builder = provider_builder.with_span_processor(xray_processor);
+        Some(exporter)
+    } else {
+        None
+    };
+
+    // If requested, also stream each batch of spans to stderr as self-describing
+    // OTLP JSON lines, via a simple (synchronous, unbuffered) exporter: unlike
+    // the other exporters above, this one's whole point is to avoid holding
+    // queued spans in memory, so there's no batch processor to configure.
+    if stream_telemetry {
+        let stream_exporter = crate::telemetry::trace_exporter::JsonSpanExporter::new();
+        provider_builder = provider_builder.with_simple_exporter(stream_exporter);
+    }
+
+    let otel_tracer_provider = provider_builder.build();
+
     use opentelemetry::trace::TracerProvider as _;
     let otel_tracer = otel_tracer_provider.tracer(env!("CARGO_PKG_NAME"));
 
@@ -66,6 +240,51 @@ pub fn init_tracing_subscriber() -> Result<Telemetry> {
     // - OpenTelemetry says they're working on adding on their own integration:
     //   https://github.com/open-telemetry/opentelemetry-rust/issues/1571#issuecomment-2258910019)
 
+    // Create our custom otel metrics exporter that queues up data until it's told to flush to a file.
+    // Fields on `tracing` events named `histogram.<name>`/`counter.<name>`/`monotonic_counter.<name>`
+    // get translated into instruments on this pipeline by `tracing_opentelemetry::MetricsLayer` below.
+    let benchmark_metrics_exporter = crate::telemetry::metrics::MetricsExporter::new();
+    let metrics_reader = opentelemetry_sdk::metrics::PeriodicReader::builder(
+        benchmark_metrics_exporter.clone(),
+        opentelemetry_sdk::runtime::Tokio,
+    )
+    // We flush on-demand from `flush_metrics_to_file`; this just backstops that.
+    .with_interval(std::time::Duration::from_secs(3600))
+    .build();
+
+    let mut meter_provider_builder = opentelemetry_sdk::metrics::MeterProvider::builder()
+        .with_resource(otel_resource())
+        .with_reader(metrics_reader);
+
+    // If requested, also stream metrics live to a collector over OTLP/gRPC,
+    // mirroring the OTLP trace processor above.
+    if let Some(endpoint) = otlp_endpoint {
+        use opentelemetry_otlp::WithExportConfig;
+
+        let otlp_metrics_exporter = opentelemetry_otlp::new_exporter()
+            .tonic()
+            .with_endpoint(endpoint)
+            .build_metrics_exporter()
+            .with_context(|| {
+                format!("failed building OTLP/gRPC metrics exporter for {endpoint}")
+            })?;
+
+        let otlp_metrics_reader = opentelemetry_sdk::metrics::PeriodicReader::builder(
+            otlp_metrics_exporter,
+            opentelemetry_sdk::runtime::Tokio,
+        )
+        .with_interval(std::time::Duration::from_secs(10))
+        .build();
+
+        meter_provider_builder = meter_provider_builder.with_reader(otlp_metrics_reader);
+    }
+
+    for view in metrics_views() {
+        meter_provider_builder = meter_provider_builder.with_view(view);
+    }
+    let meter_provider = meter_provider_builder.build();
+    opentelemetry::global::set_meter_provider(meter_provider.clone());
+
     use tracing_subscriber::prelude::*;
 
     let filter = tracing_subscriber::EnvFilter::new("info")
@@ -75,11 +294,17 @@ pub fn init_tracing_subscriber() -> Result<Telemetry> {
     tracing_subscriber::registry()
         .with(filter)
         .with(tracing_opentelemetry::OpenTelemetryLayer::new(otel_tracer))
+        .with(tracing_opentelemetry::MetricsLayer::new(
+            meter_provider.clone(),
+        ))
         .init();
 
     Ok(Telemetry {
         benchmark_trace_exporter,
         otel_tracer_provider,
+        benchmark_metrics_exporter,
+        meter_provider,
+        xray_segment_exporter,
     })
 }
 
@@ -100,4 +325,33 @@ impl Telemetry {
             eprintln!("Failed flushing telemetry traces to file: {e:?}");
         }
     }
+
+    pub fn flush_metrics_to_file(&mut self, path: &str) {
+        // Ensure all otel data has been flushed to our custom exporter
+        if let Err(e) = self.meter_provider.force_flush() {
+            // don't treat as fatal error
+            eprintln!("Failed to flush all telemetry metrics: {e:?}");
+        }
+
+        // Have our exporter write all queued data to a file
+        if let Err(e) = self.benchmark_metrics_exporter.flush_to_file(path) {
+            // don't treat as fatal error
+            eprintln!("Failed flushing telemetry metrics to file: {e:?}");
+        }
+    }
+
+    /// No-op unless `--xray` was passed to `init_tracing_subscriber`.
+    pub fn flush_xray_segments_to_file(&mut self, path: &str) {
+        let Some(exporter) = &mut self.xray_segment_exporter else {
+            return;
+        };
+
+        // `force_flush` on otel_tracer_provider (called by flush_to_file) already
+        // drained this exporter's batch processor too, since it's registered as
+        // a span processor on the same provider.
+        if let Err(e) = exporter.flush_to_file(path) {
+            // don't treat as fatal error
+            eprintln!("Failed flushing X-Ray segments to file: {e:?}");
+        }
+    }
 }
@@ -0,0 +1,64 @@
+//! Ships queued spans to a Datadog agent's trace intake (`PUT /v0.4/traces`),
+//! alongside the existing file and OTLP export paths.
+
+use core::fmt;
+use futures_util::future::BoxFuture;
+use opentelemetry_sdk::export::{self, trace::ExportResult};
+use opentelemetry_sdk::resource::Resource;
+use std::sync::{Arc, Mutex};
+
+use crate::telemetry::datadog::transform::traces_from_batches;
+use crate::telemetry::trace::SdkSpanDataBatch;
+
+#[derive(Clone)]
+pub struct DatadogExporter {
+    agent_url: Arc<str>,
+    http_client: reqwest::Client,
+    resource: Arc<Mutex<Resource>>,
+}
+
+impl fmt::Debug for DatadogExporter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("DatadogExporter")
+    }
+}
+
+impl DatadogExporter {
+    pub fn new(agent_url: &str) -> Self {
+        DatadogExporter {
+            agent_url: agent_url.trim_end_matches('/').into(),
+            http_client: reqwest::Client::new(),
+            resource: Arc::new(Mutex::new(Resource::empty())),
+        }
+    }
+}
+
+impl opentelemetry_sdk::export::trace::SpanExporter for DatadogExporter {
+    fn export(&mut self, batch: Vec<export::trace::SpanData>) -> BoxFuture<'static, ExportResult> {
+        let resource = self.resource.lock().unwrap().clone();
+        let traces = traces_from_batches(
+            vec![SdkSpanDataBatch { resource, batch }],
+            env!("CARGO_PKG_NAME"),
+        );
+
+        let url = format!("{}/v0.4/traces", self.agent_url);
+        let client = self.http_client.clone();
+
+        Box::pin(async move {
+            client
+                .put(url)
+                .json(&traces)
+                .send()
+                .await
+                .and_then(reqwest::Response::error_for_status)
+                .map_err(|e| opentelemetry::trace::TraceError::Other(Box::new(e)))?;
+            Ok(())
+        })
+    }
+
+    fn shutdown(&mut self) {}
+
+    fn set_resource(&mut self, res: &opentelemetry_sdk::Resource) {
+        *self.resource.lock().unwrap() = res.clone();
+    }
+}
@@ -0,0 +1,4 @@
+mod exporter;
+pub use exporter::DatadogExporter;
+
+pub(crate) mod transform;
@@ -0,0 +1,140 @@
+//! Maps queued spans into Datadog's `PUT /v0.4/traces` payload shape: an array of
+//! traces, each an array of spans.
+//! See: https://github.com/DataDog/datadog-agent/blob/main/pkg/trace/api/version.go
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::telemetry::common::Value;
+use crate::telemetry::trace::SdkSpanDataBatch;
+
+#[derive(Debug, Serialize)]
+pub(crate) struct DatadogSpan {
+    trace_id: u64,
+    span_id: u64,
+    #[serde(skip_serializing_if = "is_zero")]
+    parent_id: u64,
+    name: String,
+    resource: String,
+    service: String,
+    start: i64,
+    duration: i64,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    meta: BTreeMap<String, String>,
+}
+
+fn is_zero(v: &u64) -> bool {
+    *v == 0
+}
+
+/// Group queued batches into traces (one `Vec<DatadogSpan>` per distinct OTEL trace ID).
+pub(crate) fn traces_from_batches(
+    batches: Vec<SdkSpanDataBatch>,
+    fallback_service_name: &str,
+) -> Vec<Vec<DatadogSpan>> {
+    let mut by_trace: BTreeMap<u128, Vec<DatadogSpan>> = BTreeMap::new();
+
+    for batch in batches {
+        let (service, resource_meta) =
+            resource_service_and_meta(&batch.resource, fallback_service_name);
+
+        for span in batch.batch {
+            let trace_id_bytes = span.span_context.trace_id().to_bytes();
+
+            let mut meta = resource_meta.clone();
+            for kv in &span.attributes {
+                meta.insert(
+                    kv.key.as_str().to_string(),
+                    value_to_string(&Value::from(kv.value.clone())),
+                );
+            }
+
+            let datadog_span = DatadogSpan {
+                trace_id: lower_64(trace_id_bytes),
+                span_id: u64::from_be_bytes(span.span_context.span_id().to_bytes()),
+                parent_id: if span.parent_span_id == opentelemetry::trace::SpanId::INVALID {
+                    0
+                } else {
+                    u64::from_be_bytes(span.parent_span_id.to_bytes())
+                },
+                name: span.name.to_string(),
+                resource: span.name.to_string(),
+                service: service.clone(),
+                start: as_unix_nanos(span.start_time),
+                duration: as_unix_nanos(span.end_time) - as_unix_nanos(span.start_time),
+                meta,
+            };
+
+            by_trace
+                .entry(u128::from_be_bytes(trace_id_bytes))
+                .or_default()
+                .push(datadog_span);
+        }
+    }
+
+    by_trace.into_values().collect()
+}
+
+fn lower_64(trace_id_bytes: [u8; 16]) -> u64 {
+    u64::from_be_bytes(trace_id_bytes[8..16].try_into().unwrap())
+}
+
+fn as_unix_nanos(time: SystemTime) -> i64 {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos() as i64
+}
+
+/// Mirror Datadog's resource handling: promote `service.name` out of the generic
+/// resource attribute bag into the native `service` field (so it isn't duplicated as
+/// a tag), falling back to the crate name when absent.
+fn resource_service_and_meta(
+    resource: &opentelemetry_sdk::resource::Resource,
+    fallback_service_name: &str,
+) -> (String, BTreeMap<String, String>) {
+    use opentelemetry_semantic_conventions::resource::SERVICE_NAME;
+
+    let mut service = None;
+    let mut meta = BTreeMap::new();
+    for (key, value) in resource.iter() {
+        if key.as_str() == SERVICE_NAME {
+            service = Some(value_to_string(&Value::from(value.clone())));
+        } else {
+            meta.insert(
+                key.as_str().to_string(),
+                value_to_string(&Value::from(value.clone())),
+            );
+        }
+    }
+
+    (
+        service.unwrap_or_else(|| fallback_service_name.to_string()),
+        meta,
+    )
+}
+
+fn value_to_string(value: &Value) -> String {
+    match value {
+        Value::Bool(b) => b.to_string(),
+        Value::Int(i) => i.to_string(),
+        Value::Double(d) => d.to_string(),
+        Value::String(s) => s.clone(),
+        Value::Array(a) => a.iter().map(value_to_string).collect::<Vec<_>>().join(","),
+        Value::KeyValues(kvs) => kvs
+            .iter()
+            .map(|kv| format!("{kv:?}"))
+            .collect::<Vec<_>>()
+            .join(","),
+        Value::BytesValue(b) => hex_encode(b),
+    }
+}
+
+fn hex_encode(bytes: &[u8]) -> String {
+    use std::fmt::Write;
+    let mut s = String::with_capacity(bytes.len() * 2);
+    for b in bytes {
+        write!(s, "{b:02x}").unwrap();
+    }
+    s
+}
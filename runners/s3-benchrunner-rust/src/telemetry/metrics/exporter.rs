@@ -0,0 +1,73 @@
+//! A metrics analog of `crate::telemetry::trace::exporter::SpanExporter`: queues up
+//! aggregated snapshots, and flushes them to a file when it's told.
+
+use anyhow::Context;
+use async_trait::async_trait;
+use core::fmt;
+use opentelemetry_sdk::metrics::{
+    data::ResourceMetrics, exporter::PushMetricExporter, InstrumentKind, MetricResult, Temporality,
+};
+use std::{
+    fs::File,
+    io::BufWriter,
+    sync::{Arc, Mutex},
+};
+
+use crate::telemetry::metrics::transform::{ResourceMetricsData, SerdeResourceMetrics};
+
+/// An OpenTelemetry metrics exporter that queues up aggregated metric snapshots,
+/// and flushes them to a file when it's told.
+#[derive(Clone, Default)]
+pub struct MetricsExporter {
+    queued: Arc<Mutex<Vec<SerdeResourceMetrics>>>,
+}
+
+impl fmt::Debug for MetricsExporter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("MetricsExporter")
+    }
+}
+
+impl MetricsExporter {
+    pub fn new() -> Self {
+        MetricsExporter::default()
+    }
+
+    pub fn flush_to_file(&self, path: &str) -> crate::Result<()> {
+        let queued = std::mem::take(&mut *self.queued.lock().unwrap());
+
+        let data = ResourceMetricsData::new(queued);
+
+        let file = File::create_new(path)
+            .with_context(|| format!("Failed opening metrics file: {path}"))?;
+        let writer = BufWriter::new(file);
+        serde_json::to_writer(writer, &data)
+            .with_context(|| format!("Failed writing json to: {path}"))
+    }
+}
+
+#[async_trait]
+impl PushMetricExporter for MetricsExporter {
+    async fn export(&self, metrics: &mut ResourceMetrics) -> MetricResult<()> {
+        self.queued
+            .lock()
+            .unwrap()
+            .push(SerdeResourceMetrics::from(&*metrics));
+        Ok(())
+    }
+
+    async fn force_flush(&self) -> MetricResult<()> {
+        Ok(())
+    }
+
+    fn shutdown(&self) -> MetricResult<()> {
+        Ok(())
+    }
+
+    // Delta, not Cumulative: `flush_metrics_to_file` is called once per repeat
+    // run, and each run's file should reflect only that run's own contribution
+    // rather than a running total since process start.
+    fn temporality(&self, _kind: InstrumentKind) -> Temporality {
+        Temporality::Delta
+    }
+}
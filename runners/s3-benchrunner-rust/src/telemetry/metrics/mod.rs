@@ -0,0 +1,4 @@
+mod exporter;
+pub use exporter::MetricsExporter;
+
+pub(crate) mod transform;
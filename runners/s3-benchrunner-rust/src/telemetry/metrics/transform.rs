@@ -0,0 +1,177 @@
+//! Maps `opentelemetry_sdk` aggregated metrics into serde-serializable structs that
+//! mirror the OTLP metrics wire shape, reusing the `Value`/`KeyValue` machinery from
+//! `crate::telemetry::common`.
+//! See: https://github.com/open-telemetry/opentelemetry-proto/blob/main/opentelemetry/proto/metrics/v1/metrics.proto
+
+use opentelemetry_sdk::metrics::data::{
+    Histogram as SdkHistogram, HistogramDataPoint as SdkHistogramDataPoint, ResourceMetrics,
+    Sum as SdkSum, SumDataPoint as SdkSumDataPoint,
+};
+use serde::Serialize;
+
+use crate::telemetry::common::{KeyValue, Resource, Scope};
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ResourceMetricsData {
+    resource_metrics: Vec<SerdeResourceMetrics>,
+}
+
+impl ResourceMetricsData {
+    pub(crate) fn new(snapshots: Vec<SerdeResourceMetrics>) -> Self {
+        ResourceMetricsData {
+            resource_metrics: snapshots,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SerdeResourceMetrics {
+    resource: Resource,
+    scope_metrics: Vec<ScopeMetrics>,
+}
+
+impl From<&ResourceMetrics> for SerdeResourceMetrics {
+    fn from(value: &ResourceMetrics) -> Self {
+        SerdeResourceMetrics {
+            resource: Resource::from(&value.resource),
+            scope_metrics: value.scope_metrics.iter().map(ScopeMetrics::from).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ScopeMetrics {
+    scope: Scope,
+    metrics: Vec<Metric>,
+}
+
+impl From<&opentelemetry_sdk::metrics::data::ScopeMetrics> for ScopeMetrics {
+    fn from(value: &opentelemetry_sdk::metrics::data::ScopeMetrics) -> Self {
+        ScopeMetrics {
+            scope: value.scope.clone().into(),
+            metrics: value.metrics.iter().map(Metric::from).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Metric {
+    name: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    description: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    unit: String,
+    #[serde(flatten)]
+    data: MetricData,
+}
+
+impl From<&opentelemetry_sdk::metrics::data::Metric> for Metric {
+    fn from(value: &opentelemetry_sdk::metrics::data::Metric) -> Self {
+        Metric {
+            name: value.name.to_string(),
+            description: value.description.to_string(),
+            unit: value.unit.to_string(),
+            data: MetricData::from(value.data.as_any()),
+        }
+    }
+}
+
+/// Explicit-bucket histograms, for distribution metrics like per-object
+/// throughput/latency, and sums, for counters like bytes-transferred.
+/// Anything the benchmark isn't currently recording is left as `Unsupported`
+/// rather than guessed at.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase", tag = "type")]
+enum MetricData {
+    Histogram {
+        data_points: Vec<HistogramDataPoint>,
+    },
+    Sum {
+        data_points: Vec<SumDataPoint>,
+        is_monotonic: bool,
+    },
+    Unsupported,
+}
+
+impl From<&dyn std::any::Any> for MetricData {
+    fn from(value: &dyn std::any::Any) -> Self {
+        if let Some(hist) = value.downcast_ref::<SdkHistogram<f64>>() {
+            MetricData::Histogram {
+                data_points: hist
+                    .data_points
+                    .iter()
+                    .map(HistogramDataPoint::from)
+                    .collect(),
+            }
+        } else if let Some(sum) = value.downcast_ref::<SdkSum<u64>>() {
+            MetricData::Sum {
+                data_points: sum.data_points.iter().map(SumDataPoint::from).collect(),
+                is_monotonic: sum.is_monotonic,
+            }
+        } else if let Some(sum) = value.downcast_ref::<SdkSum<f64>>() {
+            MetricData::Sum {
+                data_points: sum.data_points.iter().map(SumDataPoint::from).collect(),
+                is_monotonic: sum.is_monotonic,
+            }
+        } else {
+            MetricData::Unsupported
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct HistogramDataPoint {
+    attributes: Vec<KeyValue>,
+    count: u64,
+    sum: f64,
+    min: Option<f64>,
+    max: Option<f64>,
+    /// Upper bound (inclusive) of each bucket. `bucket_counts` has one more
+    /// entry than this: its last entry counts values above the last bound.
+    explicit_bounds: Vec<f64>,
+    bucket_counts: Vec<u64>,
+}
+
+impl From<&SdkHistogramDataPoint<f64>> for HistogramDataPoint {
+    fn from(value: &SdkHistogramDataPoint<f64>) -> Self {
+        HistogramDataPoint {
+            attributes: value.attributes.iter().map(KeyValue::from).collect(),
+            count: value.count,
+            sum: value.sum,
+            min: value.min,
+            max: value.max,
+            explicit_bounds: value.bounds.clone(),
+            bucket_counts: value.bucket_counts.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SumDataPoint {
+    attributes: Vec<KeyValue>,
+    value: f64,
+}
+
+impl From<&SdkSumDataPoint<u64>> for SumDataPoint {
+    fn from(value: &SdkSumDataPoint<u64>) -> Self {
+        SumDataPoint {
+            attributes: value.attributes.iter().map(KeyValue::from).collect(),
+            value: value.value as f64,
+        }
+    }
+}
+
+impl From<&SdkSumDataPoint<f64>> for SumDataPoint {
+    fn from(value: &SdkSumDataPoint<f64>) -> Self {
+        SumDataPoint {
+            attributes: value.attributes.iter().map(KeyValue::from).collect(),
+            value: value.value,
+        }
+    }
+}
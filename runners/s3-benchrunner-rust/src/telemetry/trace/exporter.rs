@@ -13,9 +13,11 @@ use std::{
 use crate::telemetry::trace::transform::SpanData;
 use opentelemetry_sdk::resource::Resource;
 
-/// Magic number based on: In Oct 2024, downloading 1 30GiB file generated 11,000+ batches per run.
-/// This should give plenty of headroom for more tracing data and larger workloads.
-const QUEUED_BATCHES_INITIAL_CAPACITY: usize = 2_097_152;
+/// Now that spans go through a `BatchSpanProcessor` (see `telemetry::init_tracing_subscriber`)
+/// instead of a simple/synchronous processor, a 30GiB download generates a handful of
+/// batches per run rather than the 11,000+ we used to see pre-batching. This just needs
+/// to be big enough that normal runs never have to reallocate mid-benchmark.
+const QUEUED_BATCHES_INITIAL_CAPACITY: usize = 64;
 
 /// An OpenTelemetry exporter that queues up spans, and flushes them to a file when it's told
 #[derive(Clone)]
@@ -0,0 +1,4 @@
+mod exporter;
+pub use exporter::{SdkSpanDataBatch, SpanExporter};
+
+pub(crate) mod transform;
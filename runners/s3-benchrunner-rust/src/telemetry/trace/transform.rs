@@ -0,0 +1,139 @@
+//! Groups queued [`SdkSpanDataBatch`]es into the OTLP
+//! `resourceSpans -> scopeSpans -> spans` hierarchy, ready for serde serialization.
+//! See: https://github.com/open-telemetry/opentelemetry-proto/blob/main/opentelemetry/proto/trace/v1/trace.proto
+
+use std::collections::HashMap;
+use std::time::SystemTime;
+
+use serde::Serialize;
+
+use crate::telemetry::common::{
+    as_human_readable, as_unix_nano, AttributeSet, KeyValue, Resource, Scope,
+};
+use crate::telemetry::trace::exporter::SdkSpanDataBatch;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SpanData {
+    resource_spans: Vec<ResourceSpans>,
+}
+
+impl SpanData {
+    /// Group queued batches by resource, then by instrumentation scope.
+    pub(crate) fn new(batches: Vec<SdkSpanDataBatch>) -> Self {
+        let mut by_resource: HashMap<AttributeSet, (Resource, HashMap<ScopeKey, Vec<Span>>)> =
+            HashMap::new();
+
+        for batch in batches {
+            let resource_key = AttributeSet::from(&batch.resource);
+            let (_, scope_map) = by_resource
+                .entry(resource_key)
+                .or_insert_with(|| (Resource::from(&batch.resource), HashMap::new()));
+
+            for span in batch.batch {
+                let scope_key = ScopeKey::from(&span.instrumentation_lib);
+                scope_map
+                    .entry(scope_key)
+                    .or_default()
+                    .push(Span::from(span));
+            }
+        }
+
+        let resource_spans = by_resource
+            .into_values()
+            .map(|(resource, scope_map)| ResourceSpans {
+                resource,
+                scope_spans: scope_map
+                    .into_iter()
+                    .map(|(scope_key, spans)| ScopeSpans {
+                        scope: scope_key.into(),
+                        spans,
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        SpanData { resource_spans }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ResourceSpans {
+    resource: Resource,
+    scope_spans: Vec<ScopeSpans>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ScopeSpans {
+    scope: Scope,
+    spans: Vec<Span>,
+}
+
+/// Hashable stand-in for an instrumentation scope, used to group spans before
+/// converting to the serde-serializable [`Scope`].
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+struct ScopeKey {
+    name: String,
+    version: Option<String>,
+}
+
+impl From<&opentelemetry_sdk::InstrumentationLibrary> for ScopeKey {
+    fn from(value: &opentelemetry_sdk::InstrumentationLibrary) -> Self {
+        ScopeKey {
+            name: value.name.to_string(),
+            version: value.version.as_ref().map(|v| v.to_string()),
+        }
+    }
+}
+
+impl From<ScopeKey> for Scope {
+    fn from(value: ScopeKey) -> Self {
+        opentelemetry_sdk::InstrumentationLibrary::builder(value.name)
+            .with_version(value.version.unwrap_or_default())
+            .build()
+            .into()
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Span {
+    trace_id: String,
+    span_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parent_span_id: Option<String>,
+    name: String,
+    #[serde(serialize_with = "as_unix_nano")]
+    start_time_unix_nano: SystemTime,
+    #[serde(serialize_with = "as_unix_nano")]
+    end_time_unix_nano: SystemTime,
+    #[serde(serialize_with = "as_human_readable")]
+    start_time: SystemTime,
+    #[serde(serialize_with = "as_human_readable")]
+    end_time: SystemTime,
+    attributes: Vec<KeyValue>,
+}
+
+impl From<opentelemetry_sdk::export::trace::SpanData> for Span {
+    fn from(value: opentelemetry_sdk::export::trace::SpanData) -> Self {
+        let parent_span_id = if value.parent_span_id == opentelemetry::trace::SpanId::INVALID {
+            None
+        } else {
+            Some(value.parent_span_id.to_string())
+        };
+
+        Span {
+            trace_id: value.span_context.trace_id().to_string(),
+            span_id: value.span_context.span_id().to_string(),
+            parent_span_id,
+            name: value.name.into_owned(),
+            start_time_unix_nano: value.start_time,
+            end_time_unix_nano: value.end_time,
+            start_time: value.start_time,
+            end_time: value.end_time,
+            attributes: value.attributes.into_iter().map(KeyValue::from).collect(),
+        }
+    }
+}
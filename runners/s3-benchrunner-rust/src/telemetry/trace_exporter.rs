@@ -3,6 +3,8 @@
 use futures_util::future::BoxFuture;
 use serde::Serialize;
 
+use crate::telemetry::trace::SdkSpanDataBatch;
+
 pub struct JsonSpanExporter {
     writer: Option<Box<dyn std::io::Write + Send + Sync>>,
     otel_resource: opentelemetry_sdk::Resource,
@@ -63,18 +65,28 @@ impl core::fmt::Debug for JsonSpanExporter {
     }
 }
 
-/// Transformed trace data that can be serialized
+/// Transformed trace data that can be serialized.
+/// Mirrors the `resourceSpans -> scopeSpans -> spans` shape used by
+/// `flush_to_file`'s `crate::telemetry::trace::transform::SpanData`, so each
+/// exported line is a complete, self-describing OTLP JSON object.
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
 struct SerdeSpanData {
-    // resource_spans: Vec<SerdeResourceSpans>,
+    #[serde(flatten)]
+    inner: crate::telemetry::trace::transform::SpanData,
 }
 
 impl SerdeSpanData {
     fn new(
-        _otel_spans: Vec<opentelemetry_sdk::export::trace::SpanData>,
-        _otel_resource: &opentelemetry_sdk::Resource,
+        otel_spans: Vec<opentelemetry_sdk::export::trace::SpanData>,
+        otel_resource: &opentelemetry_sdk::Resource,
     ) -> Self {
-        SerdeSpanData {}
+        let batch = SdkSpanDataBatch {
+            resource: otel_resource.clone(),
+            batch: otel_spans,
+        };
+        SerdeSpanData {
+            inner: crate::telemetry::trace::transform::SpanData::new(vec![batch]),
+        }
     }
 }
@@ -0,0 +1,67 @@
+//! An exporter alongside `crate::telemetry::trace::SpanExporter` that queues up spans,
+//! and transforms them into newline-delimited X-Ray segment documents when flushed,
+//! ready for the X-Ray `PutTraceSegments` API.
+
+use anyhow::Context;
+use core::fmt;
+use futures_util::future::BoxFuture;
+use opentelemetry_sdk::export::{self, trace::ExportResult};
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    sync::{Arc, Mutex},
+};
+
+use crate::telemetry::xray::transform::Segment;
+use opentelemetry_sdk::resource::Resource;
+
+/// An OpenTelemetry exporter that queues up spans, and flushes them to a file
+/// as X-Ray segment documents when it's told.
+#[derive(Clone)]
+pub struct SegmentExporter {
+    queued_spans: Arc<Mutex<Vec<export::trace::SpanData>>>,
+}
+
+impl fmt::Debug for SegmentExporter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SegmentExporter")
+    }
+}
+
+impl opentelemetry_sdk::export::trace::SpanExporter for SegmentExporter {
+    fn export(&mut self, batch: Vec<export::trace::SpanData>) -> BoxFuture<'static, ExportResult> {
+        self.queued_spans.lock().unwrap().extend(batch);
+        Box::pin(std::future::ready(ExportResult::Ok(())))
+    }
+
+    fn shutdown(&mut self) {}
+
+    // X-Ray segments don't carry OTEL resource attributes; nothing to record here.
+    fn set_resource(&mut self, _res: &Resource) {}
+}
+
+impl SegmentExporter {
+    pub fn new() -> SegmentExporter {
+        SegmentExporter {
+            queued_spans: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    pub fn flush_to_file(&mut self, path: &str) -> crate::Result<()> {
+        let queued_spans = std::mem::take(&mut *self.queued_spans.lock().unwrap());
+
+        let file = File::create_new(path)
+            .with_context(|| format!("Failed opening X-Ray segment file: {path}"))?;
+        let mut writer = BufWriter::new(file);
+
+        for span in queued_spans {
+            let segment = Segment::from(span);
+            serde_json::to_writer(&mut writer, &segment)
+                .with_context(|| format!("Failed writing segment json to: {path}"))?;
+            writer
+                .write_all(b"\n")
+                .with_context(|| format!("Failed writing to: {path}"))?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,28 @@
+use opentelemetry::trace::{SpanId, TraceId};
+use opentelemetry_sdk::trace::IdGenerator;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Generates AWS X-Ray-compatible trace IDs: the first 32 bits are the big-endian
+/// unix epoch seconds of span start (the rest random), matching X-Ray's
+/// `1-<8 hex epoch>-<24 hex random>` root ID format. Span IDs are ordinary random IDs.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct XrayIdGenerator;
+
+impl IdGenerator for XrayIdGenerator {
+    fn new_trace_id(&self) -> TraceId {
+        let epoch_secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs() as u32;
+
+        let mut bytes = [0u8; 16];
+        bytes[0..4].copy_from_slice(&epoch_secs.to_be_bytes());
+        fastrand::Rng::new().fill(&mut bytes[4..]);
+
+        TraceId::from_bytes(bytes)
+    }
+
+    fn new_span_id(&self) -> SpanId {
+        SpanId::from_bytes(fastrand::Rng::new().u64(..).to_be_bytes())
+    }
+}
@@ -0,0 +1,7 @@
+mod id_generator;
+pub use id_generator::XrayIdGenerator;
+
+mod exporter;
+pub use exporter::SegmentExporter;
+
+pub(crate) mod transform;
@@ -0,0 +1,68 @@
+//! Maps `opentelemetry_sdk` span data into AWS X-Ray segment documents.
+//! See: https://docs.aws.amazon.com/xray/latest/devguide/xray-api-segmentdocuments.html
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::telemetry::common::Value;
+
+#[derive(Debug, Serialize)]
+pub(crate) struct Segment {
+    id: String,
+    trace_id: String,
+    name: String,
+    start_time: f64,
+    end_time: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parent_id: Option<String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    annotations: BTreeMap<String, Value>,
+}
+
+impl From<opentelemetry_sdk::export::trace::SpanData> for Segment {
+    fn from(value: opentelemetry_sdk::export::trace::SpanData) -> Self {
+        let trace_id_bytes = value.span_context.trace_id().to_bytes();
+        // First 32 bits are the epoch seconds `XrayIdGenerator` encoded; the rest is random.
+        let trace_id = format!(
+            "1-{}-{}",
+            to_hex(&trace_id_bytes[0..4]),
+            to_hex(&trace_id_bytes[4..16])
+        );
+
+        let parent_id = if value.parent_span_id == opentelemetry::trace::SpanId::INVALID {
+            None
+        } else {
+            Some(to_hex(&value.parent_span_id.to_bytes()))
+        };
+
+        Segment {
+            id: to_hex(&value.span_context.span_id().to_bytes()),
+            trace_id,
+            name: value.name.into_owned(),
+            start_time: as_epoch_secs(value.start_time),
+            end_time: as_epoch_secs(value.end_time),
+            parent_id,
+            annotations: value
+                .attributes
+                .into_iter()
+                .map(|kv| (kv.key.as_str().to_string(), Value::from(kv.value)))
+                .collect(),
+        }
+    }
+}
+
+fn as_epoch_secs(time: SystemTime) -> f64 {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs_f64()
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    use std::fmt::Write;
+    let mut s = String::with_capacity(bytes.len() * 2);
+    for b in bytes {
+        write!(s, "{b:02x}").unwrap();
+    }
+    s
+}
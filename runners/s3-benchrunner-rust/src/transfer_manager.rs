@@ -1,8 +1,10 @@
 use std::{cmp::min, path::PathBuf, sync::Arc};
 
-use anyhow::Context;
+use anyhow::{anyhow, Context};
 use async_trait::async_trait;
-use aws_sdk_s3::types::ChecksumAlgorithm;
+use aws_sdk_s3::types::{
+    ChecksumAlgorithm, ChecksumType, Delete, ObjectAttributes, ObjectIdentifier,
+};
 use aws_sdk_s3_transfer_manager::{
     io::InputStream,
     operation::upload::ChecksumStrategy,
@@ -15,9 +17,13 @@ use tokio::task::JoinSet;
 use tracing::{info_span, Instrument};
 
 use crate::{
-    skip_benchmark, BenchmarkConfig, Result, RunBenchmark, TaskAction, TaskConfig, PART_SIZE,
+    bytes_to_gigabits, effective_part_size, skip_benchmark, BenchmarkConfig, DiscoverConfig,
+    Result, RunBenchmark, RunStats, TaskAction, TaskConfig,
 };
 
+// S3's hard ceiling on the number of keys in a single DeleteObjects request.
+const MAX_DELETE_BATCH: usize = 1000;
+
 /// Benchmark runner using aws-s3-transfer-manager
 #[derive(Clone)]
 pub struct TransferManagerRunner {
@@ -26,13 +32,35 @@ pub struct TransferManagerRunner {
 
 struct Handle {
     config: BenchmarkConfig,
-    transfer_manager: aws_sdk_s3_transfer_manager::Client,
+    // Download and upload get their own client, each with its own
+    // `TargetThroughput` budget (see `BenchmarkConfig::throughput_for_action`),
+    // so they run as independent concurrency domains and a mixed workload's
+    // slow side can't starve or penalize the other.
+    download_transfer_manager: aws_sdk_s3_transfer_manager::Client,
+    upload_transfer_manager: aws_sdk_s3_transfer_manager::Client,
+    // Plain S3 client for operations the transfer manager doesn't cover,
+    // e.g. batched DeleteObjects.
+    s3_client: aws_sdk_s3::Client,
     random_data_for_upload: Bytes,
     transfer_path: Option<String>,
+    // Set by `request_shutdown()` on a Ctrl-C from `main`. `shutdown_notify`
+    // wakes up every transfer currently racing `wait_for_shutdown()` (see
+    // below) so a single large, long-running transfer winds down as soon as
+    // Ctrl-C arrives, not just at its next completion.
+    shutdown: std::sync::atomic::AtomicBool,
+    shutdown_notify: tokio::sync::Notify,
 }
 
 impl TransferManagerRunner {
-    pub async fn new(config: BenchmarkConfig) -> TransferManagerRunner {
+    pub async fn new(mut config: BenchmarkConfig) -> Result<TransferManagerRunner> {
+        let s3_client = new_s3_client().await;
+
+        // Materialize discovered tasks before anything below reads
+        // `config.workload.tasks` (byte totals, throughput split, etc.).
+        if let Some(discover) = &config.workload.discover {
+            config.workload.tasks = discover_tasks(&s3_client, &config.bucket, discover).await?;
+        }
+
         // Create random buffer to upload
         let upload_data_size: usize = if config.workload.files_on_disk {
             0
@@ -50,26 +78,34 @@ impl TransferManagerRunner {
         };
         let random_data_for_upload = new_random_bytes(upload_data_size);
 
-        let tm_config = aws_sdk_s3_transfer_manager::from_env()
-            .concurrency(ConcurrencyMode::TargetThroughput(
-                TargetThroughput::new_gigabits_per_sec(
-                    config.target_throughput_gigabits_per_sec as u64,
-                ),
-            ))
-            .part_size(PartSize::Target(PART_SIZE))
-            .load()
-            .await;
-
-        let transfer_manager = aws_sdk_s3_transfer_manager::Client::new(tm_config);
+        let download_transfer_manager = new_transfer_manager_client(
+            config.throughput_for_action(TaskAction::Download),
+            config.base_part_size,
+            config.concurrency_override,
+            config.buffer_size_override,
+        )
+        .await;
+        let upload_transfer_manager = new_transfer_manager_client(
+            config.throughput_for_action(TaskAction::Upload),
+            config.base_part_size,
+            config.concurrency_override,
+            config.buffer_size_override,
+        )
+        .await;
+
         let transfer_path = find_common_parent_dir(&config);
-        TransferManagerRunner {
+        Ok(TransferManagerRunner {
             handle: Arc::new(Handle {
                 config,
-                transfer_manager,
+                download_transfer_manager,
+                upload_transfer_manager,
+                s3_client,
                 random_data_for_upload,
                 transfer_path,
+                shutdown: std::sync::atomic::AtomicBool::new(false),
+                shutdown_notify: tokio::sync::Notify::new(),
             }),
-        }
+        })
     }
 
     async fn run_task(self, task_i: usize) -> Result<()> {
@@ -86,51 +122,152 @@ impl TransferManagerRunner {
                     .instrument(info_span!("upload", key = task_config.key))
                     .await
             }
+            // Deletes are batched and run outside the per-task JoinSet (see
+            // `RunBenchmark::run`), so they never reach `run_task`.
+            TaskAction::Delete => {
+                unreachable!("delete tasks are batched, not spawned individually")
+            }
         }
     }
-    async fn download_objects(&self) -> Result<()> {
+
+    /// Delete `keys` via batched `DeleteObjects` calls, chunked to S3's
+    /// `MAX_DELETE_BATCH`-key limit per request. Used both for the workload's
+    /// own `TaskAction::Delete` tasks and for automatic teardown.
+    async fn delete_objects(&self, keys: &[String]) -> Result<()> {
+        for chunk in keys.chunks(MAX_DELETE_BATCH) {
+            let objects = chunk
+                .iter()
+                .map(|key| ObjectIdentifier::builder().key(key).build())
+                .collect::<std::result::Result<Vec<_>, _>>()?;
+
+            let delete = Delete::builder().set_objects(Some(objects)).build()?;
+
+            let output = self
+                .handle
+                .s3_client
+                .delete_objects()
+                .bucket(&self.config().bucket)
+                .delete(delete)
+                .send()
+                .await
+                .with_context(|| format!("failed deleting batch of {} object(s)", chunk.len()))?;
+
+            if !output.errors().is_empty() {
+                let first_error = &output.errors()[0];
+                return Err(anyhow!(
+                    "DeleteObjects reported {} failed key(s), e.g. {:?}: {} {}",
+                    output.errors().len(),
+                    first_error.key().unwrap_or_default(),
+                    first_error.code().unwrap_or_default(),
+                    first_error.message().unwrap_or_default(),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns `false` instead of erroring if `request_shutdown()` wins the
+    /// race against the transfer, since the directory-wide transfer-manager
+    /// API doesn't expose how many of its objects had already completed -
+    /// the caller can't report partial bytes for this path, only that it was
+    /// cut short.
+    async fn download_objects(&self) -> Result<bool> {
         let path = self.handle.transfer_path.as_ref().unwrap();
         let dest = PathBuf::from(path);
         let download_objects_handle = self
             .handle
-            .transfer_manager
+            .download_transfer_manager
             .download_objects()
             .bucket(&self.config().bucket)
             .key_prefix(path)
             .destination(&dest)
             .send()
             .await?;
-        download_objects_handle.join().await?;
-        Ok(())
+        tokio::select! {
+            result = download_objects_handle.join() => result.map(|_| true),
+            _ = self.wait_for_shutdown() => Ok(false),
+        }
     }
 
-    async fn upload_objects(&self) -> Result<()> {
+    /// See `download_objects` for why this returns `bool` instead of `()`.
+    async fn upload_objects(&self) -> Result<bool> {
         let path = self.handle.transfer_path.as_ref().unwrap();
         let upload_objects_handle = self
             .handle
-            .transfer_manager
+            .upload_transfer_manager
             .upload_objects()
             .bucket(&self.config().bucket)
             .key_prefix(path)
             .source(path)
             .send()
             .await?;
-        upload_objects_handle.join().await?;
-        Ok(())
+        tokio::select! {
+            result = upload_objects_handle.join() => result.map(|_| true),
+            _ = self.wait_for_shutdown() => Ok(false),
+        }
     }
 
     async fn download(&self, task_config: &TaskConfig) -> Result<()> {
         let key = &task_config.key;
+        let task_start = std::time::Instant::now();
+
+        let part_size = effective_part_size(self.config().base_part_size, task_config.size);
 
         let mut download_handle = self
             .handle
-            .transfer_manager
+            .download_transfer_manager
             .download()
             .bucket(&self.config().bucket)
             .key(key)
+            .part_size(PartSize::Target(part_size))
             .initiate()
             .with_context(|| format!("failed starting download: {key}"))?;
 
+        // Opt-in: if the workload names a checksum algorithm, verify the
+        // downloaded bytes against S3's stored checksum instead of only
+        // timing the transfer, to catch silent corruption under high
+        // throughput rather than trusting the byte count alone.
+        let mut verifier = match self.config().workload.checksum.as_deref() {
+            Some(algorithm_name) => {
+                let expected = match algorithm_name.to_ascii_uppercase().as_str() {
+                    "CRC32C" => download_handle.checksum_crc32_c(),
+                    "CRC32" => download_handle.checksum_crc32(),
+                    "SHA1" => download_handle.checksum_sha1(),
+                    "SHA256" => download_handle.checksum_sha256(),
+                    other => {
+                        return Err(anyhow!(
+                            "unsupported checksum algorithm for verification: {other}"
+                        ))
+                    }
+                }
+                .map(str::to_string)
+                .with_context(|| {
+                    format!("object has no stored {algorithm_name} checksum to verify against: {key}")
+                })?;
+
+                let composite =
+                    matches!(download_handle.checksum_type(), Some(ChecksumType::Composite));
+                // Composite checksums are a checksum-of-checksums over the
+                // object's *upload-time* part boundaries, which have no
+                // relation to this download's own part size (set above from
+                // this run's `base_part_size`/`--part-size`) - fetch the
+                // real ones instead of assuming they match.
+                let part_sizes = if composite {
+                    fetch_part_sizes(&self.handle.s3_client, &self.config().bucket, key).await?
+                } else {
+                    Vec::new()
+                };
+                Some(ChecksumVerifier::new(
+                    algorithm_name,
+                    expected,
+                    composite,
+                    part_sizes,
+                )?)
+            }
+            None => None,
+        };
+
         // if files_on_disk: open file for writing
         let mut dest_file = if self.config().workload.files_on_disk {
             let file = File::create(key)
@@ -158,6 +295,10 @@ impl TransferManagerRunner {
             total_size += chunk_size as u64;
             seq += 1;
 
+            if let Some(verifier) = &mut verifier {
+                verifier.update(chunk.chunk());
+            }
+
             if let Some(dest_file) = &mut dest_file {
                 dest_file
                     .write_all_buf(&mut chunk)
@@ -168,11 +309,18 @@ impl TransferManagerRunner {
 
         assert_eq!(total_size, task_config.size);
 
+        if let Some(verifier) = verifier {
+            verifier.finish(key)?;
+        }
+
+        record_task_metrics(task_config.size, task_start.elapsed());
+
         Ok(())
     }
 
     async fn upload(&self, task_config: &TaskConfig) -> Result<()> {
         let key = &task_config.key;
+        let task_start = std::time::Instant::now();
 
         let stream = if self.config().workload.files_on_disk {
             InputStream::from_path(key).with_context(|| "Failed to create stream")?
@@ -192,14 +340,17 @@ impl TransferManagerRunner {
             _ => None,
         };
 
+        let part_size = effective_part_size(self.config().base_part_size, task_config.size);
+
         let upload_handle = self
             .handle
-            .transfer_manager
+            .upload_transfer_manager
             .upload()
             .bucket(&self.config().bucket)
             .key(key)
             .body(stream)
             .set_checksum_strategy(checksum_strategy)
+            .part_size(PartSize::Target(part_size))
             .initiate()?;
 
         upload_handle
@@ -207,19 +358,194 @@ impl TransferManagerRunner {
             .await
             .with_context(|| format!("failed uploading: {key}"))?;
 
+        record_task_metrics(task_config.size, task_start.elapsed());
+
+        Ok(())
+    }
+}
+
+// Record per-object throughput/latency distributions, and running totals,
+// for a single completed upload/download task.
+//
+// These fields are picked up by `tracing_opentelemetry::MetricsLayer` (registered
+// in `telemetry::init_tracing_subscriber`) and turned into OTLP metric instruments:
+// `histogram.*` -> histogram, `counter.*` -> up-down counter, `monotonic_counter.*` -> monotonic counter.
+// This lets us get p50/p99 throughput across objects, not just one Gb/s print per run.
+fn record_task_metrics(size: u64, elapsed: std::time::Duration) {
+    let secs = elapsed.as_secs_f64().max(f64::EPSILON);
+    let throughput_gbps = bytes_to_gigabits(size) / secs;
+    let latency_ms = elapsed.as_secs_f64() * 1000.0;
+
+    tracing::info!(
+        histogram.throughput_gbps = throughput_gbps,
+        histogram.latency_ms = latency_ms,
+        monotonic_counter.bytes_transferred = size,
+        monotonic_counter.objects_completed = 1_u64,
+        "task complete"
+    );
+}
+
+// Streaming hasher for one of the checksum algorithms `WorkloadConfig::checksum`
+// can name. Kept as a plain enum (mirroring `ChecksumAlgorithm::from` above)
+// rather than a trait object, since the set of algorithms is small and fixed.
+enum ChecksumHasher {
+    Crc32c(u32),
+    Crc32(crc32fast::Hasher),
+    Sha1(sha1::Sha1),
+    Sha256(sha2::Sha256),
+}
+
+impl ChecksumHasher {
+    fn new(algorithm_name: &str) -> Result<Self> {
+        match algorithm_name.to_ascii_uppercase().as_str() {
+            "CRC32C" => Ok(ChecksumHasher::Crc32c(0)),
+            "CRC32" => Ok(ChecksumHasher::Crc32(crc32fast::Hasher::new())),
+            "SHA1" => Ok(ChecksumHasher::Sha1(<sha1::Sha1 as sha1::Digest>::new())),
+            "SHA256" => Ok(ChecksumHasher::Sha256(<sha2::Sha256 as sha2::Digest>::new())),
+            other => Err(anyhow!("unsupported checksum algorithm: {other}")),
+        }
+    }
+
+    fn update(&mut self, bytes: &[u8]) {
+        match self {
+            ChecksumHasher::Crc32c(state) => *state = crc32c::crc32c_append(*state, bytes),
+            ChecksumHasher::Crc32(hasher) => hasher.update(bytes),
+            ChecksumHasher::Sha1(hasher) => sha1::Digest::update(hasher, bytes),
+            ChecksumHasher::Sha256(hasher) => sha2::Digest::update(hasher, bytes),
+        }
+    }
+
+    fn finalize_bytes(self) -> Vec<u8> {
+        match self {
+            ChecksumHasher::Crc32c(state) => state.to_be_bytes().to_vec(),
+            ChecksumHasher::Crc32(hasher) => hasher.finalize().to_be_bytes().to_vec(),
+            ChecksumHasher::Sha1(hasher) => sha1::Digest::finalize(hasher).to_vec(),
+            ChecksumHasher::Sha256(hasher) => sha2::Digest::finalize(hasher).to_vec(),
+        }
+    }
+
+    fn finalize_base64(self) -> String {
+        use base64::Engine as _;
+        base64::engine::general_purpose::STANDARD.encode(self.finalize_bytes())
+    }
+}
+
+/// Verifies downloaded bytes against S3's stored checksum as chunks stream
+/// in. For a whole-object checksum this is just a running hash. For a
+/// composite multipart checksum, each segment is hashed separately according
+/// to `part_sizes` (the object's *actual* upload-time part boundaries, from
+/// `fetch_part_sizes` - not whatever part size this download happens to be
+/// using), then the concatenation of the raw part digests is hashed again to
+/// reproduce S3's checksum-of-checksums.
+struct ChecksumVerifier {
+    algorithm_name: String,
+    expected: String,
+    composite: bool,
+    part_sizes: std::collections::VecDeque<u64>,
+    current_part_size: u64,
+    bytes_in_part: u64,
+    part_hasher: ChecksumHasher,
+    part_digests: Vec<u8>,
+}
+
+impl ChecksumVerifier {
+    fn new(
+        algorithm_name: &str,
+        expected: String,
+        composite: bool,
+        part_sizes: Vec<u64>,
+    ) -> Result<Self> {
+        let mut part_sizes: std::collections::VecDeque<u64> = part_sizes.into();
+        let current_part_size = part_sizes.pop_front().unwrap_or(u64::MAX);
+        Ok(ChecksumVerifier {
+            algorithm_name: algorithm_name.to_string(),
+            expected,
+            composite,
+            part_sizes,
+            current_part_size,
+            bytes_in_part: 0,
+            part_hasher: ChecksumHasher::new(algorithm_name)?,
+            part_digests: Vec::new(),
+        })
+    }
+
+    fn update(&mut self, mut bytes: &[u8]) {
+        if !self.composite {
+            self.part_hasher.update(bytes);
+            return;
+        }
+
+        // Split the incoming chunk across part boundaries so each part is
+        // hashed independently, matching how S3 computed the composite
+        // checksum at upload time.
+        while !bytes.is_empty() {
+            let remaining_in_part = (self.current_part_size - self.bytes_in_part) as usize;
+            let take = remaining_in_part.min(bytes.len());
+            self.part_hasher.update(&bytes[..take]);
+            self.bytes_in_part += take as u64;
+            bytes = &bytes[take..];
+
+            if self.bytes_in_part == self.current_part_size {
+                self.finish_part();
+            }
+        }
+    }
+
+    fn finish_part(&mut self) {
+        // ChecksumHasher::new can't fail for an algorithm we already built
+        // one of successfully, so this unwrap just satisfies the signature.
+        let finished_hasher = ChecksumHasher::new(&self.algorithm_name).unwrap();
+        let finished = std::mem::replace(&mut self.part_hasher, finished_hasher);
+        self.part_digests.extend(finished.finalize_bytes());
+        self.bytes_in_part = 0;
+        // If the stream somehow produced more bytes than `part_sizes`
+        // accounted for, treat the rest as one final open-ended part
+        // instead of looping forever on a zero-size boundary; the
+        // composite checksum computed in `finish` simply won't match S3's
+        // in that case, which is the correct outcome for truly mismatched
+        // data.
+        self.current_part_size = self.part_sizes.pop_front().unwrap_or(u64::MAX);
+    }
+
+    fn finish(mut self, key: &str) -> Result<()> {
+        let actual = if self.composite {
+            if self.bytes_in_part > 0 {
+                self.finish_part();
+            }
+            let mut whole_object_hasher = ChecksumHasher::new(&self.algorithm_name)?;
+            whole_object_hasher.update(&self.part_digests);
+            whole_object_hasher.finalize_base64()
+        } else {
+            self.part_hasher.finalize_base64()
+        };
+
+        if actual != self.expected {
+            return Err(anyhow!(
+                "checksum mismatch for {key}: expected {} {}, got {actual}",
+                self.algorithm_name,
+                self.expected
+            ));
+        }
+
         Ok(())
     }
 }
 
 #[async_trait]
 impl RunBenchmark for TransferManagerRunner {
-    async fn run(&self) -> Result<()> {
+    async fn run(&self) -> Result<RunStats> {
         let workload_config = &self.config().workload;
 
         match &self.handle.transfer_path {
             Some(transfer_path) => {
-                // Use the objects API to download/upload directory directly
-                match workload_config.tasks[0].action {
+                // Use the objects API to download/upload directory directly.
+                // `find_common_parent_dir` only takes this path for a
+                // homogeneous workload, so all bytes belong to one action.
+                let action = workload_config.tasks[0].action;
+                let total_bytes: u64 = workload_config.tasks.iter().map(|t| t.size).sum();
+                let start = std::time::Instant::now();
+
+                let completed = match action {
                     TaskAction::Download => {
                         self.download_objects()
                             .instrument(info_span!("download-directory", directory = transfer_path))
@@ -236,31 +562,319 @@ impl RunBenchmark for TransferManagerRunner {
                             .instrument(info_span!("upload-directory", directory = transfer_path))
                             .await?
                     }
+                    // `find_common_parent_dir` never takes the directory-wide
+                    // path for a delete workload.
+                    TaskAction::Delete => {
+                        unreachable!("delete workloads don't use directory-wide transfer")
+                    }
+                };
+
+                let secs = start.elapsed().as_secs_f64();
+                // `download_objects`/`upload_objects` don't expose partial
+                // progress, so a `request_shutdown()` that cuts one short
+                // reports zero bytes/secs for this domain rather than a
+                // guess - same as the `RunStats::default()` main.rs falls
+                // back to when the shutdown grace period itself times out.
+                if !completed {
+                    return Ok(RunStats::default());
                 }
+                Ok(match action {
+                    TaskAction::Download => RunStats {
+                        download_secs: secs,
+                        download_bytes: total_bytes,
+                        ..Default::default()
+                    },
+                    TaskAction::Upload => RunStats {
+                        upload_secs: secs,
+                        upload_bytes: total_bytes,
+                        ..Default::default()
+                    },
+                    TaskAction::Delete => {
+                        unreachable!("delete workloads don't use directory-wide transfer")
+                    }
+                })
             }
             None => {
                 // Spawn concurrent tasks for all uploads/downloads.
                 // We want the benchmark to fail fast if anything goes wrong,
-                // so we're using a JoinSet.
-                let mut task_set: JoinSet<Result<()>> = JoinSet::new();
+                // so we're using a JoinSet. Each task is tagged with its
+                // action and size so we can track, per concurrency domain,
+                // when the last download and the last upload finished, and
+                // count bytes only for tasks that actually completed (a
+                // `request_shutdown()` can cut the rest short - see
+                // `wait_for_shutdown` below).
+                let mut task_set: JoinSet<(TaskAction, u64, Option<Result<()>>)> = JoinSet::new();
+                let run_start = std::time::Instant::now();
+                // Deletes run as batched `DeleteObjects` calls after the
+                // download/upload tasks finish, not as one spawned task per
+                // key, so collect their keys instead of spawning here.
+                let mut delete_keys: Vec<String> = Vec::new();
+
                 // Iterate through all the tasks to download/upload each object.
                 for i in 0..workload_config.tasks.len() {
-                    let task = self.clone().run_task(i);
-                    task_set.spawn(task.instrument(tracing::Span::current()));
+                    let task_config = &workload_config.tasks[i];
+                    let action = task_config.action;
+                    let size = task_config.size;
+                    if action == TaskAction::Delete {
+                        delete_keys.push(task_config.key.clone());
+                        continue;
+                    }
+
+                    // Race the transfer itself against `wait_for_shutdown()`,
+                    // rather than only checking the flag once this (or some
+                    // other) task completes: a single large, long-running
+                    // transfer now winds down as soon as Ctrl-C arrives.
+                    let runner = self.clone();
+                    let task = self.clone().run_task(i).instrument(tracing::Span::current());
+                    task_set.spawn(async move {
+                        let task_result = tokio::select! {
+                            result = task => Some(result),
+                            _ = runner.wait_for_shutdown() => None,
+                        };
+                        (action, size, task_result)
+                    });
                 }
 
+                let mut download_secs = 0.0_f64;
+                let mut download_bytes = 0u64;
+                let mut upload_secs = 0.0_f64;
+                let mut upload_bytes = 0u64;
                 while let Some(join_result) = task_set.join_next().await {
-                    let task_result = join_result.unwrap();
-                    task_result?;
+                    match join_result {
+                        // `None` means `wait_for_shutdown()` won the race, i.e.
+                        // `request_shutdown()` was called before this task
+                        // finished; its bytes never landed, so they don't
+                        // count toward the total.
+                        Ok((_, _, None)) => {}
+                        Ok((action, size, Some(task_result))) => {
+                            task_result?;
+
+                            let elapsed = run_start.elapsed().as_secs_f64();
+                            match action {
+                                TaskAction::Download => {
+                                    download_secs = download_secs.max(elapsed);
+                                    download_bytes += size;
+                                }
+                                TaskAction::Upload => {
+                                    upload_secs = upload_secs.max(elapsed);
+                                    upload_bytes += size;
+                                }
+                                TaskAction::Delete => {
+                                    unreachable!("delete tasks never reach the JoinSet")
+                                }
+                            }
+                        }
+                        Err(join_err) => panic!("benchmark task failed: {join_err}"),
+                    }
                 }
+
+                let shutdown_requested = self
+                    .handle
+                    .shutdown
+                    .load(std::sync::atomic::Ordering::Relaxed);
+
+                // Skip the batched delete too if we're winding down early;
+                // whatever's left will just get batched on the next attempt.
+                let (delete_secs, delete_count) = if shutdown_requested || delete_keys.is_empty() {
+                    (0.0, 0)
+                } else {
+                    let delete_start = std::time::Instant::now();
+                    let count = delete_keys.len();
+                    self.delete_objects(&delete_keys)
+                        .instrument(info_span!("delete", count))
+                        .await?;
+                    (delete_start.elapsed().as_secs_f64(), count as u64)
+                };
+
+                Ok(RunStats {
+                    download_secs,
+                    download_bytes,
+                    upload_secs,
+                    upload_bytes,
+                    delete_secs,
+                    delete_count,
+                })
             }
         }
-        Ok(())
     }
 
     fn config(&self) -> &BenchmarkConfig {
         &self.handle.config
     }
+
+    /// Delete every object the workload's upload tasks would have created,
+    /// via the same batched `DeleteObjects` path as `TaskAction::Delete`, so
+    /// a big upload workload doesn't leave thousands of orphaned objects
+    /// behind when run with automatic teardown enabled.
+    async fn teardown(&self) -> Result<()> {
+        let keys: Vec<String> = self
+            .config()
+            .workload
+            .tasks
+            .iter()
+            .filter(|task| task.action == TaskAction::Upload)
+            .map(|task| task.key.clone())
+            .collect();
+
+        if keys.is_empty() {
+            return Ok(());
+        }
+
+        let count = keys.len();
+        self.delete_objects(&keys)
+            .instrument(info_span!("teardown", count))
+            .await
+    }
+
+    fn request_shutdown(&self) {
+        self.handle
+            .shutdown
+            .store(true, std::sync::atomic::Ordering::Relaxed);
+        // Wake up every transfer currently awaiting `wait_for_shutdown()`
+        // instead of leaving it to notice only at its next completion.
+        self.handle.shutdown_notify.notify_waiters();
+    }
+}
+
+impl TransferManagerRunner {
+    /// Resolves once `request_shutdown()` has been called (immediately, if
+    /// it already has been). Meant to be raced via `tokio::select!` against
+    /// an in-flight transfer so a single large download/upload winds down as
+    /// soon as Ctrl-C arrives, instead of only being checked between tasks.
+    async fn wait_for_shutdown(&self) {
+        loop {
+            // Register for the next notification before checking the flag,
+            // so a `request_shutdown()` landing between the check and the
+            // `.await` below can't be missed.
+            let notified = self.handle.shutdown_notify.notified();
+            if self
+                .handle
+                .shutdown
+                .load(std::sync::atomic::Ordering::Relaxed)
+            {
+                return;
+            }
+            notified.await;
+        }
+    }
+}
+
+async fn new_s3_client() -> aws_sdk_s3::Client {
+    let sdk_config = aws_config::load_defaults(aws_config::BehaviorVersion::latest()).await;
+    aws_sdk_s3::Client::new(&sdk_config)
+}
+
+/// Fetch the actual per-part byte sizes S3 used for this object's multipart
+/// upload, via `GetObjectAttributes`, paginating by part number as needed.
+/// Composite checksum verification needs these exact boundaries: the
+/// current download's own part size has no relation to how the object was
+/// originally uploaded.
+async fn fetch_part_sizes(
+    s3_client: &aws_sdk_s3::Client,
+    bucket: &str,
+    key: &str,
+) -> Result<Vec<u64>> {
+    let mut sizes = Vec::new();
+    let mut part_number_marker = None;
+    loop {
+        let output = s3_client
+            .get_object_attributes()
+            .bucket(bucket)
+            .key(key)
+            .object_attributes(ObjectAttributes::ObjectParts)
+            .set_part_number_marker(part_number_marker)
+            .send()
+            .await
+            .with_context(|| format!("failed fetching part sizes for: {key}"))?;
+
+        let Some(object_parts) = output.object_parts() else {
+            break;
+        };
+
+        for part in object_parts.parts() {
+            sizes.push(part.size().unwrap_or(0).max(0) as u64);
+        }
+
+        if !object_parts.is_truncated().unwrap_or(false) {
+            break;
+        }
+        part_number_marker = object_parts.next_part_number_marker().map(str::to_string);
+    }
+
+    Ok(sizes)
+}
+
+/// Turn every object under `discover.prefix` into a `TaskConfig`, paginating
+/// `ListObjectsV2` as needed. Called once, from `TransferManagerRunner::new`,
+/// before the `max_repeat_count`-driven loop in `main` starts - every repeat
+/// run reuses the resulting `config.workload.tasks` rather than re-listing.
+async fn discover_tasks(
+    s3_client: &aws_sdk_s3::Client,
+    bucket: &str,
+    discover: &DiscoverConfig,
+) -> Result<Vec<TaskConfig>> {
+    let mut tasks = Vec::new();
+    let mut continuation_token = None;
+    loop {
+        let output = s3_client
+            .list_objects_v2()
+            .bucket(bucket)
+            .prefix(&discover.prefix)
+            .set_continuation_token(continuation_token)
+            .send()
+            .await
+            .with_context(|| format!("failed listing objects under prefix: {}", discover.prefix))?;
+
+        for object in output.contents() {
+            let key = object
+                .key()
+                .ok_or_else(|| {
+                    anyhow!("listed object under prefix {} has no key", discover.prefix)
+                })?
+                .to_string();
+            let size = object.size().unwrap_or(0).max(0) as u64;
+            tasks.push(TaskConfig {
+                action: discover.action,
+                key,
+                size,
+            });
+        }
+
+        continuation_token = output.next_continuation_token().map(str::to_string);
+        if continuation_token.is_none() {
+            break;
+        }
+    }
+
+    Ok(tasks)
+}
+
+async fn new_transfer_manager_client(
+    target_throughput_gigabits_per_sec: f64,
+    part_size: u64,
+    concurrency_override: Option<usize>,
+    buffer_size_override: Option<u64>,
+) -> aws_sdk_s3_transfer_manager::Client {
+    // A fixed concurrency override replaces the default TargetThroughput mode
+    // entirely, since the two are alternative ways of bounding the same
+    // in-flight-part budget.
+    let concurrency = match concurrency_override {
+        Some(concurrency) => ConcurrencyMode::Explicit(concurrency),
+        None => ConcurrencyMode::TargetThroughput(TargetThroughput::new_gigabits_per_sec(
+            target_throughput_gigabits_per_sec as u64,
+        )),
+    };
+
+    let mut builder = aws_sdk_s3_transfer_manager::from_env()
+        .concurrency(concurrency)
+        .part_size(PartSize::Target(part_size));
+
+    if let Some(buffer_size) = buffer_size_override {
+        builder = builder.read_buffer_size(buffer_size as usize);
+    }
+
+    let tm_config = builder.load().await;
+    aws_sdk_s3_transfer_manager::Client::new(tm_config)
 }
 
 /// Find the common parent directory for all tasks.
@@ -270,19 +884,30 @@ fn find_common_parent_dir(config: &BenchmarkConfig) -> Option<String> {
     {
         let first_task = &config.workload.tasks[0];
 
+        // Deletes don't have a directory-wide transfer API like
+        // download_objects()/upload_objects(), so they always go through
+        // the per-task path in `RunBenchmark::run`.
+        if first_task.action == TaskAction::Delete {
+            return None;
+        }
+
         // Find the common parents directory for all the tasks.
         // If there is no common parent, we can't use the same directory for downloads.
         let mut common_root = std::path::Path::new(&first_task.key).parent()?;
         for task in &config.workload.tasks {
+            if task.action != first_task.action {
+                // Mixed download+upload workloads run as independent
+                // concurrency domains (see `RunBenchmark::run`), not through
+                // a single directory-wide transfer.
+                return None;
+            }
+
             let task_path = std::path::Path::new(&task.key);
             common_root = common_root.ancestors().find(|ancestor| {
                 task_path
                     .ancestors()
                     .any(|task_ancestor| task_ancestor == *ancestor)
             })?;
-            if task.action != first_task.action {
-                panic!("Can't use directory for both download and upload");
-            }
         }
 
         // S3Express requires that the prefix must end with delimiter